@@ -1,8 +1,9 @@
 use crate::blocks::{
-    browser::Browser, code::Code, curl::Curl, data::Data, input::Input, llm::LLM, map::Map,
-    reduce::Reduce, search::Search,
+    browser::Browser, code::Code, curl::Curl, data::Data, graphql::GraphQL, input::Input,
+    llm::LLM, map::Map, reduce::Reduce, search::Search, xml::Xml,
 };
 use crate::project::Project;
+use crate::run::oauth2::OAuth2TokenCache;
 use crate::run::{Credentials, RunConfig};
 use crate::stores::store::Store;
 use crate::utils::ParseError;
@@ -31,6 +32,16 @@ pub struct InputState {
     pub index: usize,
 }
 
+// Controls whether blocks perform their real (side-effecting) work, record it for later replay,
+// or serve it back from a prior capture without re-executing it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionMode {
+    Live,
+    Capture,
+    Replay,
+}
+
 // Env is serialized when passed to code blocks. RunConfig.credentials are not serialized.
 #[derive(Serialize, Clone)]
 pub struct Env {
@@ -38,12 +49,15 @@ pub struct Env {
     pub state: HashMap<String, Value>,
     pub input: InputState,
     pub map: Option<MapState>,
+    pub execution_mode: ExecutionMode,
     #[serde(skip_serializing)]
     pub store: Box<dyn Store + Sync + Send>,
     #[serde(skip_serializing)]
     pub project: Project,
     #[serde(skip_serializing)]
     pub credentials: Credentials,
+    #[serde(skip_serializing)]
+    pub oauth2_tokens: OAuth2TokenCache,
 }
 
 // pub enum Expectations {
@@ -63,6 +77,8 @@ pub enum BlockType {
     Search,
     Curl,
     Browser,
+    GraphQL,
+    Xml,
 }
 
 impl ToString for BlockType {
@@ -77,6 +93,8 @@ impl ToString for BlockType {
             BlockType::Search => String::from("search"),
             BlockType::Curl => String::from("curl"),
             BlockType::Browser => String::from("browser"),
+            BlockType::GraphQL => String::from("graphql"),
+            BlockType::Xml => String::from("xml"),
         }
     }
 }
@@ -94,6 +112,8 @@ impl FromStr for BlockType {
             "search" => Ok(BlockType::Search),
             "curl" => Ok(BlockType::Curl),
             "browser" => Ok(BlockType::Browser),
+            "graphql" => Ok(BlockType::GraphQL),
+            "xml" => Ok(BlockType::Xml),
             _ => Err(ParseError::with_message("Unknown BlockType"))?,
         }
     }
@@ -114,6 +134,48 @@ pub trait Block {
 
     fn clone_box(&self) -> Box<dyn Block + Sync + Send>;
     fn as_any(&self) -> &dyn Any;
+
+    // Cache key used by capture/replay execution. Combines the block's own content hash with its
+    // name and type, `InputState.index` (which top-level input this run is on), and the current
+    // `MapState.iteration` when inside a `Map` (since iteration numbers restart per input, the
+    // index and the iteration must both be present or two different inputs' iteration 0 collide
+    // on the same key).
+    fn capture_replay_key(&self, name: &str, env: &Env) -> String {
+        format!(
+            "{}:{}:{}:{}:{}",
+            name,
+            self.block_type().to_string(),
+            self.inner_hash(),
+            env.input.index,
+            match &env.map {
+                Some(map_state) => format!("map:{}", map_state.iteration),
+                None => String::from("map:none"),
+            }
+        )
+    }
+
+    // Blocks that perform side effects (LLM, Search, Curl, Browser, ...) should call this at the
+    // top of `execute` and return the cached value immediately on a hit. Always returns `None` in
+    // `Live`/`Capture` mode; in `Replay` mode returns `None` only on a genuine cache miss.
+    async fn replayed_value(&self, name: &str, env: &Env) -> Result<Option<Value>> {
+        if env.execution_mode != ExecutionMode::Replay {
+            return Ok(None);
+        }
+        env.store
+            .get_block_capture(&self.capture_replay_key(name, env))
+            .await
+    }
+
+    // Blocks that perform side effects should call this with the `Value` they are about to
+    // return from `execute`. No-op outside `Capture` mode.
+    async fn capture_value(&self, name: &str, env: &Env, value: &Value) -> Result<()> {
+        if env.execution_mode != ExecutionMode::Capture {
+            return Ok(());
+        }
+        env.store
+            .store_block_capture(&self.capture_replay_key(name, env), value)
+            .await
+    }
 }
 
 impl Clone for Box<dyn Block + Sync + Send> {
@@ -164,6 +226,27 @@ pub fn parse_block(t: BlockType, block_pair: Pair<Rule>) -> Result<Box<dyn Block
         BlockType::Search => Ok(Box::new(Search::parse(block_pair)?)),
         BlockType::Curl => Ok(Box::new(Curl::parse(block_pair)?)),
         BlockType::Browser => Ok(Box::new(Browser::parse(block_pair)?)),
+        BlockType::GraphQL => Ok(Box::new(GraphQL::parse(block_pair)?)),
+        BlockType::Xml => Ok(Box::new(Xml::parse(block_pair)?)),
+    }
+}
+
+// Re-parses a block's `(name, BlockType)` pair from a reloaded spec and returns the fresh
+// `Box<dyn Block>` only if it actually differs from `current` (by type or by `inner_hash()`).
+// Returning `None` on no change lets a hot-reload watcher swap in edited block definitions
+// between iterations of a long-running `Map` without disturbing blocks whose spec didn't change
+// (and whose captured state in `Env::store` is therefore still valid for replay).
+pub fn reload_block(
+    t: BlockType,
+    block_pair: Pair<Rule>,
+    current: &(dyn Block + Sync + Send),
+) -> Result<Option<Box<dyn Block + Sync + Send>>> {
+    let reloaded = parse_block(t, block_pair)?;
+    if reloaded.block_type() != current.block_type() || reloaded.inner_hash() != current.inner_hash()
+    {
+        Ok(Some(reloaded))
+    } else {
+        Ok(None)
     }
 }
 
@@ -183,6 +266,47 @@ pub fn find_variables(text: &str) -> Vec<(String, String)> {
         .collect::<Vec<_>>()
 }
 
+// Walks `value` through `path`, one dot-separated segment at a time: a segment that parses as a
+// `usize` indexes into a JSON array, otherwise it looks up an object field. `base` is the
+// already-resolved prefix (e.g. `BLOCK` or `BLOCK.a`) and is used only to report which exact
+// segment failed.
+fn resolve_variable_path<'a>(value: &'a Value, path: &[&str], base: &str) -> Result<&'a Value> {
+    let mut current = value;
+    let mut resolved = base.to_string();
+
+    for segment in path {
+        current = match segment.parse::<usize>() {
+            Ok(index) => {
+                let array = current.as_array().ok_or_else(|| {
+                    anyhow!(
+                        "`{}` is not an array, cannot index it with `{}`",
+                        resolved,
+                        segment
+                    )
+                })?;
+                array
+                    .get(index)
+                    .ok_or_else(|| anyhow!("index {} out of bounds in `{}`", index, resolved))?
+            }
+            Err(_) => {
+                let object = current.as_object().ok_or_else(|| {
+                    anyhow!(
+                        "`{}` is not an object, cannot look up key `{}` in it",
+                        resolved,
+                        segment
+                    )
+                })?;
+                object
+                    .get(*segment)
+                    .ok_or_else(|| anyhow!("key `{}` not found in `{}`", segment, resolved))?
+            }
+        };
+        resolved = format!("{}.{}", resolved, segment);
+    }
+
+    Ok(current)
+}
+
 pub fn replace_variables_in_string(text: &str, field: &str, env: &Env) -> Result<String> {
     let variables = find_variables(text);
 
@@ -191,7 +315,8 @@ pub fn replace_variables_in_string(text: &str, field: &str, env: &Env) -> Result
     variables
         .iter()
         .map(|(name, key)| {
-            // Check that the block output exists and is an object.
+            // Check that the block output exists and is an object (only the first path segment
+            // is constrained this way; nested segments may resolve to any JSON type).
             let output = env
                 .state
                 .get(name)
@@ -205,23 +330,17 @@ pub fn replace_variables_in_string(text: &str, field: &str, env: &Env) -> Result
                     field
                 ))?;
             }
-            let output = output.as_object().unwrap();
 
-            if !output.contains_key(key) {
-                Err(anyhow!(
-                    "Key `{}` is not present in block `{}` output",
-                    key,
-                    name
-                ))?;
-            }
-            // Check that output[key] is a string.
-            if !output.get(key).unwrap().is_string() {
-                Err(anyhow!("`{}.{}` is not a string", name, key,))?;
-            }
-            result = result.replace(
-                &format!("${{{}.{}}}", name, key),
-                &output[key].as_str().unwrap(),
-            );
+            let segments = key.split('.').collect::<Vec<_>>();
+            let resolved = resolve_variable_path(output, &segments, name)?;
+
+            // Strings are substituted raw (unquoted); any other JSON type is substituted as its
+            // serialized form so structured block outputs can be templated directly.
+            let replacement = match resolved {
+                Value::String(s) => s.clone(),
+                other => serde_json::to_string(other)?,
+            };
+            result = result.replace(&format!("${{{}.{}}}", name, key), &replacement);
 
             Ok(())
         })
@@ -267,9 +386,11 @@ mod tests {
                 index: 0,
             },
             map: None,
+            execution_mode: ExecutionMode::Live,
             project: Project::new_from_id(1),
             store: Box::new(SQLiteStore::new_in_memory()?),
             credentials: Credentials::new(),
+            oauth2_tokens: OAuth2TokenCache::new(),
         };
         assert_eq!(
             replace_variables_in_string(
@@ -282,4 +403,162 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn replace_variables_in_string_structured_test() -> Result<()> {
+        let env = Env {
+            config: RunConfig {
+                blocks: HashMap::new(),
+            },
+            state: serde_json::from_str(
+                r#"{"BLOCK":{"a":{"b":[{"c":"hello"},{"c":42}]},"flag":true}}"#,
+            )
+            .unwrap(),
+            input: InputState {
+                value: None,
+                index: 0,
+            },
+            map: None,
+            execution_mode: ExecutionMode::Live,
+            project: Project::new_from_id(1),
+            store: Box::new(SQLiteStore::new_in_memory()?),
+            credentials: Credentials::new(),
+            oauth2_tokens: OAuth2TokenCache::new(),
+        };
+
+        // Nested object/array path resolving to a string is substituted raw.
+        assert_eq!(
+            replace_variables_in_string(r#"${BLOCK.a.b.0.c}"#, "foo", &env)?,
+            "hello".to_string()
+        );
+        // A non-string leaf (number) is substituted as its JSON form.
+        assert_eq!(
+            replace_variables_in_string(r#"${BLOCK.a.b.1.c}"#, "foo", &env)?,
+            "42".to_string()
+        );
+        // A non-leaf path resolving to an object/array is substituted as its JSON form.
+        assert_eq!(
+            replace_variables_in_string(r#"${BLOCK.a}"#, "foo", &env)?,
+            r#"{"b":[{"c":"hello"},{"c":42}]}"#.to_string()
+        );
+
+        // Missing key reports the exact failing segment.
+        match replace_variables_in_string(r#"${BLOCK.a.z}"#, "foo", &env) {
+            Err(e) => assert!(e.to_string().contains("key `z` not found in `BLOCK.a`")),
+            Ok(_) => panic!("expected an error"),
+        }
+        // Out of bounds index reports the exact failing segment.
+        match replace_variables_in_string(r#"${BLOCK.a.b.5}"#, "foo", &env) {
+            Err(e) => assert!(e
+                .to_string()
+                .contains("index 5 out of bounds in `BLOCK.a.b`")),
+            Ok(_) => panic!("expected an error"),
+        }
+
+        Ok(())
+    }
+
+    // A minimal side-effecting block used only to exercise `replayed_value`/`capture_value`
+    // against a real `Store` without pulling in an HTTP-backed block (Curl/Search/LLM/Browser).
+    #[derive(Clone)]
+    struct DummyBlock {
+        content: String,
+    }
+
+    #[async_trait]
+    impl Block for DummyBlock {
+        fn block_type(&self) -> BlockType {
+            BlockType::Curl
+        }
+
+        fn inner_hash(&self) -> String {
+            self.content.clone()
+        }
+
+        async fn execute(
+            &self,
+            _name: &str,
+            _env: &Env,
+            _event_sender: Option<UnboundedSender<Value>>,
+        ) -> Result<Value> {
+            unreachable!("not exercised by this test")
+        }
+
+        fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+            Box::new(self.clone())
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn test_env(execution_mode: ExecutionMode, store: Box<dyn Store + Sync + Send>) -> Env {
+        Env {
+            config: RunConfig {
+                blocks: HashMap::new(),
+            },
+            state: HashMap::new(),
+            input: InputState {
+                value: None,
+                index: 0,
+            },
+            map: None,
+            execution_mode,
+            project: Project::new_from_id(1),
+            store,
+            credentials: Credentials::new(),
+            oauth2_tokens: OAuth2TokenCache::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn capture_then_replay_round_trip_test() -> Result<()> {
+        let block = DummyBlock {
+            content: String::from("hello"),
+        };
+        let store: Box<dyn Store + Sync + Send> = Box::new(SQLiteStore::new_in_memory()?);
+
+        let capture_env = test_env(ExecutionMode::Capture, store.clone());
+        let value = serde_json::json!({"status": 200, "body": "ok"});
+        block.capture_value("BLOCK", &capture_env, &value).await?;
+
+        let replay_env = test_env(ExecutionMode::Replay, store.clone());
+        assert_eq!(
+            block.replayed_value("BLOCK", &replay_env).await?,
+            Some(value)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replayed_value_miss_outside_replay_test() -> Result<()> {
+        let block = DummyBlock {
+            content: String::from("hello"),
+        };
+        let store: Box<dyn Store + Sync + Send> = Box::new(SQLiteStore::new_in_memory()?);
+
+        // `Live` and `Capture` never consult the store, regardless of its contents.
+        let live_env = test_env(ExecutionMode::Live, store.clone());
+        assert_eq!(block.replayed_value("BLOCK", &live_env).await?, None);
+
+        // Two different top-level inputs must not collide on the same replay key.
+        let mut other_input_env = test_env(ExecutionMode::Capture, store.clone());
+        other_input_env.input.index = 1;
+        block
+            .capture_value(
+                "BLOCK",
+                &other_input_env,
+                &serde_json::json!({"input": 1}),
+            )
+            .await?;
+
+        let first_input_replay_env = test_env(ExecutionMode::Replay, store);
+        assert_eq!(
+            block.replayed_value("BLOCK", &first_input_replay_env).await?,
+            None
+        );
+
+        Ok(())
+    }
 }
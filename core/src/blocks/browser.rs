@@ -0,0 +1,106 @@
+use crate::blocks::block::{parse_pair, replace_variables_in_string, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use pest::iterators::Pair;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone)]
+pub struct Browser {
+    url: String,
+    selector: Option<String>,
+}
+
+impl Browser {
+    pub fn parse(block_pair: Pair<Rule>) -> Result<Self> {
+        let mut url: Option<String> = None;
+        let mut selector: Option<String> = None;
+
+        for pair in block_pair.into_inner() {
+            match pair.as_rule() {
+                Rule::pair => {
+                    let (key, value) = parse_pair(pair)?;
+                    match key.as_str() {
+                        "url" => url = Some(value),
+                        "selector" => selector = Some(value),
+                        _ => Err(anyhow!("Unexpected `{}` in `browser` block", key))?,
+                    }
+                }
+                Rule::expected => {
+                    // `browser` blocks do not support output expectations.
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if url.is_none() {
+            Err(anyhow!("Missing required `url` in `browser` block"))?;
+        }
+
+        Ok(Browser {
+            url: url.unwrap(),
+            selector,
+        })
+    }
+}
+
+#[async_trait]
+impl Block for Browser {
+    fn block_type(&self) -> BlockType {
+        BlockType::Browser
+    }
+
+    fn inner_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update("browser".as_bytes());
+        hasher.update(self.url.as_bytes());
+        if let Some(selector) = &self.selector {
+            hasher.update(selector.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        env: &Env,
+        _event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        if let Some(cached) = self.replayed_value(name, env).await? {
+            return Ok(cached);
+        }
+
+        let url = replace_variables_in_string(&self.url, "url", env)?;
+
+        let html = reqwest::Client::new().get(&url).send().await?.text().await?;
+
+        let value = match &self.selector {
+            Some(selector) => {
+                let selector = replace_variables_in_string(selector, "selector", env)?;
+                let document = scraper::Html::parse_document(&html);
+                let parsed_selector = scraper::Selector::parse(&selector)
+                    .map_err(|_| anyhow!("Invalid `selector` `{}` in `browser` block", selector))?;
+                let matches = document
+                    .select(&parsed_selector)
+                    .map(|el| el.text().collect::<String>())
+                    .collect::<Vec<_>>();
+                serde_json::json!({ "url": url, "matches": matches })
+            }
+            None => serde_json::json!({ "url": url, "html": html }),
+        };
+
+        self.capture_value(name, env, &value).await?;
+
+        Ok(value)
+    }
+
+    fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
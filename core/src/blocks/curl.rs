@@ -0,0 +1,147 @@
+use crate::blocks::block::{parse_pair, replace_variables_in_string, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use pest::iterators::Pair;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::collections::HashMap;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone)]
+pub struct Curl {
+    method: String,
+    url: String,
+    headers: Option<String>,
+    body: Option<String>,
+    // Name of an OAuth2 credential (resolved from `env.credentials`) to fetch a bearer token from
+    // and inject as `Authorization: Bearer <token>`.
+    credentials: Option<String>,
+}
+
+impl Curl {
+    pub fn parse(block_pair: Pair<Rule>) -> Result<Self> {
+        let mut method: Option<String> = None;
+        let mut url: Option<String> = None;
+        let mut headers: Option<String> = None;
+        let mut body: Option<String> = None;
+        let mut credentials: Option<String> = None;
+
+        for pair in block_pair.into_inner() {
+            match pair.as_rule() {
+                Rule::pair => {
+                    let (key, value) = parse_pair(pair)?;
+                    match key.as_str() {
+                        "method" => method = Some(value),
+                        "url" => url = Some(value),
+                        "headers" => headers = Some(value),
+                        "body" => body = Some(value),
+                        "credentials" => credentials = Some(value),
+                        _ => Err(anyhow!("Unexpected `{}` in `curl` block", key))?,
+                    }
+                }
+                Rule::expected => {
+                    // `curl` blocks do not support output expectations.
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if url.is_none() {
+            Err(anyhow!("Missing required `url` in `curl` block"))?;
+        }
+
+        Ok(Curl {
+            method: method.unwrap_or_else(|| String::from("GET")),
+            url: url.unwrap(),
+            headers,
+            body,
+            credentials,
+        })
+    }
+}
+
+#[async_trait]
+impl Block for Curl {
+    fn block_type(&self) -> BlockType {
+        BlockType::Curl
+    }
+
+    fn inner_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update("curl".as_bytes());
+        hasher.update(self.method.as_bytes());
+        hasher.update(self.url.as_bytes());
+        if let Some(headers) = &self.headers {
+            hasher.update(headers.as_bytes());
+        }
+        if let Some(body) = &self.body {
+            hasher.update(body.as_bytes());
+        }
+        if let Some(credentials) = &self.credentials {
+            hasher.update(credentials.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        env: &Env,
+        _event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        if let Some(cached) = self.replayed_value(name, env).await? {
+            return Ok(cached);
+        }
+
+        let method = replace_variables_in_string(&self.method, "method", env)?;
+        let url = replace_variables_in_string(&self.url, "url", env)?;
+
+        let client = reqwest::Client::new();
+        let mut req = client.request(
+            reqwest::Method::from_bytes(method.as_bytes())
+                .map_err(|e| anyhow!("Invalid `method` `{}` in `curl` block: {}", method, e))?,
+            &url,
+        );
+
+        if let Some(headers) = &self.headers {
+            let headers = replace_variables_in_string(headers, "headers", env)?;
+            let headers: HashMap<String, String> = serde_json::from_str(&headers)
+                .map_err(|e| anyhow!("Invalid `headers` JSON in `curl` block: {}", e))?;
+            for (k, v) in headers {
+                req = req.header(k, v);
+            }
+        }
+
+        if let Some(body) = &self.body {
+            req = req.body(replace_variables_in_string(body, "body", env)?);
+        }
+
+        if let Some(credentials_name) = &self.credentials {
+            let token = env
+                .credentials
+                .oauth2_bearer_token(credentials_name, &env.oauth2_tokens)
+                .await?;
+            req = req.bearer_auth(token);
+        }
+
+        let res = req.send().await?;
+        let status = res.status().as_u16();
+        let text = res.text().await?;
+        let body: Value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+
+        let value = serde_json::json!({ "status": status, "body": body });
+
+        self.capture_value(name, env, &value).await?;
+
+        Ok(value)
+    }
+
+    fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,163 @@
+use crate::blocks::block::{parse_pair, replace_variables_in_string, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use pest::iterators::Pair;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::collections::HashMap;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone)]
+pub struct GraphQL {
+    endpoint: String,
+    query: String,
+    variables: Option<String>,
+    headers: Option<String>,
+    // Name of an OAuth2 credential (resolved from `env.credentials`) to fetch a bearer token from
+    // and inject as `Authorization: Bearer <token>`.
+    credentials: Option<String>,
+}
+
+impl GraphQL {
+    pub fn parse(block_pair: Pair<Rule>) -> Result<Self> {
+        let mut endpoint: Option<String> = None;
+        let mut query: Option<String> = None;
+        let mut variables: Option<String> = None;
+        let mut headers: Option<String> = None;
+        let mut credentials: Option<String> = None;
+
+        for pair in block_pair.into_inner() {
+            match pair.as_rule() {
+                Rule::pair => {
+                    let (key, value) = parse_pair(pair)?;
+                    match key.as_str() {
+                        "endpoint" => endpoint = Some(value),
+                        "query" => query = Some(value),
+                        "variables" => variables = Some(value),
+                        "headers" => headers = Some(value),
+                        "credentials" => credentials = Some(value),
+                        _ => Err(anyhow!("Unexpected `{}` in `graphql` block", key))?,
+                    }
+                }
+                Rule::expected => {
+                    // `graphql` blocks do not support output expectations.
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if endpoint.is_none() {
+            Err(anyhow!("Missing required `endpoint` in `graphql` block"))?;
+        }
+        if query.is_none() {
+            Err(anyhow!("Missing required `query` in `graphql` block"))?;
+        }
+
+        Ok(GraphQL {
+            endpoint: endpoint.unwrap(),
+            query: query.unwrap(),
+            variables,
+            headers,
+            credentials,
+        })
+    }
+}
+
+#[async_trait]
+impl Block for GraphQL {
+    fn block_type(&self) -> BlockType {
+        BlockType::GraphQL
+    }
+
+    fn inner_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update("graphql".as_bytes());
+        hasher.update(self.endpoint.as_bytes());
+        hasher.update(self.query.as_bytes());
+        if let Some(variables) = &self.variables {
+            hasher.update(variables.as_bytes());
+        }
+        if let Some(headers) = &self.headers {
+            hasher.update(headers.as_bytes());
+        }
+        if let Some(credentials) = &self.credentials {
+            hasher.update(credentials.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        env: &Env,
+        _event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        if let Some(cached) = self.replayed_value(name, env).await? {
+            return Ok(cached);
+        }
+
+        let endpoint = replace_variables_in_string(&self.endpoint, "endpoint", env)?;
+        let query = replace_variables_in_string(&self.query, "query", env)?;
+
+        let variables: Value = match &self.variables {
+            Some(v) => {
+                let v = replace_variables_in_string(v, "variables", env)?;
+                serde_json::from_str(&v)
+                    .map_err(|e| anyhow!("Invalid `variables` JSON in `graphql` block: {}", e))?
+            }
+            None => json!({}),
+        };
+
+        let mut req = reqwest::Client::new()
+            .post(&endpoint)
+            .json(&json!({ "query": query, "variables": variables }));
+
+        if let Some(headers) = &self.headers {
+            let headers = replace_variables_in_string(headers, "headers", env)?;
+            let headers: HashMap<String, String> = serde_json::from_str(&headers)
+                .map_err(|e| anyhow!("Invalid `headers` JSON in `graphql` block: {}", e))?;
+            for (k, v) in headers {
+                req = req.header(k, v);
+            }
+        }
+
+        if let Some(credentials_name) = &self.credentials {
+            let token = env
+                .credentials
+                .oauth2_bearer_token(credentials_name, &env.oauth2_tokens)
+                .await?;
+            req = req.bearer_auth(token);
+        }
+
+        let res = req.send().await?;
+        let body: Value = res.json().await?;
+
+        if let Some(errors) = body.get("errors").and_then(|e| e.as_array()) {
+            if !errors.is_empty() {
+                Err(anyhow!(
+                    "GraphQL endpoint `{}` returned errors: {}",
+                    endpoint,
+                    Value::Array(errors.clone())
+                ))?;
+            }
+        }
+
+        let data = body
+            .get("data")
+            .ok_or_else(|| anyhow!("GraphQL response from `{}` is missing `data`", endpoint))?
+            .clone();
+
+        self.capture_value(name, env, &data).await?;
+
+        Ok(data)
+    }
+
+    fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
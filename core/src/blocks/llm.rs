@@ -0,0 +1,126 @@
+use crate::blocks::block::{parse_pair, replace_variables_in_string, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use pest::iterators::Pair;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone)]
+pub struct LLM {
+    model: String,
+    prompt: String,
+    max_tokens: usize,
+    temperature: f64,
+}
+
+impl LLM {
+    pub fn parse(block_pair: Pair<Rule>) -> Result<Self> {
+        let mut model: Option<String> = None;
+        let mut prompt: Option<String> = None;
+        let mut max_tokens: usize = 256;
+        let mut temperature: f64 = 0.7;
+
+        for pair in block_pair.into_inner() {
+            match pair.as_rule() {
+                Rule::pair => {
+                    let (key, value) = parse_pair(pair)?;
+                    match key.as_str() {
+                        "model" => model = Some(value),
+                        "prompt" => prompt = Some(value),
+                        "max_tokens" => {
+                            max_tokens = value.parse::<usize>().map_err(|e| {
+                                anyhow!("Invalid `max_tokens` in `llm` block: {}", e)
+                            })?
+                        }
+                        "temperature" => {
+                            temperature = value.parse::<f64>().map_err(|e| {
+                                anyhow!("Invalid `temperature` in `llm` block: {}", e)
+                            })?
+                        }
+                        _ => Err(anyhow!("Unexpected `{}` in `llm` block", key))?,
+                    }
+                }
+                Rule::expected => {
+                    // `llm` blocks do not support output expectations.
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if model.is_none() {
+            Err(anyhow!("Missing required `model` in `llm` block"))?;
+        }
+        if prompt.is_none() {
+            Err(anyhow!("Missing required `prompt` in `llm` block"))?;
+        }
+
+        Ok(LLM {
+            model: model.unwrap(),
+            prompt: prompt.unwrap(),
+            max_tokens,
+            temperature,
+        })
+    }
+}
+
+#[async_trait]
+impl Block for LLM {
+    fn block_type(&self) -> BlockType {
+        BlockType::LLM
+    }
+
+    fn inner_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update("llm".as_bytes());
+        hasher.update(self.model.as_bytes());
+        hasher.update(self.prompt.as_bytes());
+        hasher.update(self.max_tokens.to_string().as_bytes());
+        hasher.update(self.temperature.to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        env: &Env,
+        _event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        if let Some(cached) = self.replayed_value(name, env).await? {
+            return Ok(cached);
+        }
+
+        let prompt = replace_variables_in_string(&self.prompt, "prompt", env)?;
+        let api_key = env
+            .credentials
+            .get("llm_api_key")
+            .ok_or_else(|| anyhow!("Missing `llm_api_key` credential"))?;
+
+        let res = reqwest::Client::new()
+            .post("https://api.openai.com/v1/completions")
+            .bearer_auth(api_key)
+            .json(&serde_json::json!({
+                "model": self.model,
+                "prompt": prompt,
+                "max_tokens": self.max_tokens,
+                "temperature": self.temperature,
+            }))
+            .send()
+            .await?;
+
+        let value: Value = res.json().await?;
+
+        self.capture_value(name, env, &value).await?;
+
+        Ok(value)
+    }
+
+    fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
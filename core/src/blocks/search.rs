@@ -0,0 +1,113 @@
+use crate::blocks::block::{parse_pair, replace_variables_in_string, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use pest::iterators::Pair;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone)]
+pub struct Search {
+    provider: String,
+    query: String,
+    num_results: usize,
+}
+
+impl Search {
+    pub fn parse(block_pair: Pair<Rule>) -> Result<Self> {
+        let mut provider: Option<String> = None;
+        let mut query: Option<String> = None;
+        let mut num_results: usize = 10;
+
+        for pair in block_pair.into_inner() {
+            match pair.as_rule() {
+                Rule::pair => {
+                    let (key, value) = parse_pair(pair)?;
+                    match key.as_str() {
+                        "provider" => provider = Some(value),
+                        "query" => query = Some(value),
+                        "num_results" => {
+                            num_results = value.parse::<usize>().map_err(|e| {
+                                anyhow!("Invalid `num_results` in `search` block: {}", e)
+                            })?
+                        }
+                        _ => Err(anyhow!("Unexpected `{}` in `search` block", key))?,
+                    }
+                }
+                Rule::expected => {
+                    // `search` blocks do not support output expectations.
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if query.is_none() {
+            Err(anyhow!("Missing required `query` in `search` block"))?;
+        }
+
+        Ok(Search {
+            provider: provider.unwrap_or_else(|| String::from("serpapi")),
+            query: query.unwrap(),
+            num_results,
+        })
+    }
+}
+
+#[async_trait]
+impl Block for Search {
+    fn block_type(&self) -> BlockType {
+        BlockType::Search
+    }
+
+    fn inner_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update("search".as_bytes());
+        hasher.update(self.provider.as_bytes());
+        hasher.update(self.query.as_bytes());
+        hasher.update(self.num_results.to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        env: &Env,
+        _event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        if let Some(cached) = self.replayed_value(name, env).await? {
+            return Ok(cached);
+        }
+
+        let query = replace_variables_in_string(&self.query, "query", env)?;
+        let api_key = env
+            .credentials
+            .get(&format!("{}_api_key", self.provider))
+            .ok_or_else(|| anyhow!("Missing `{}_api_key` credential", self.provider))?;
+
+        let res = reqwest::Client::new()
+            .get("https://serpapi.com/search")
+            .query(&[
+                ("engine", self.provider.as_str()),
+                ("q", query.as_str()),
+                ("num", self.num_results.to_string().as_str()),
+                ("api_key", api_key.as_str()),
+            ])
+            .send()
+            .await?;
+
+        let value: Value = res.json().await?;
+
+        self.capture_value(name, env, &value).await?;
+
+        Ok(value)
+    }
+
+    fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,248 @@
+use crate::blocks::block::{parse_pair, replace_variables_in_string, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use pest::iterators::Pair;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use tokio::sync::mpsc::UnboundedSender;
+
+// Bound on XML element nesting depth for `node_to_value`'s recursion, so a maliciously or
+// accidentally deeply-nested document (e.g. a Curl/Browser response we don't control) returns an
+// error instead of overflowing the stack.
+const MAX_NESTING_DEPTH: usize = 256;
+
+// Converts an XML element node into a JSON `Value`: attributes are surfaced as
+// `{attribute_prefix}{name}` keys, repeated sibling elements collapse into an array, and any
+// non-whitespace text content is surfaced under `text_key` (an element with no attributes and no
+// child elements becomes the bare text string instead, or `null` when empty). Namespace prefixes
+// are dropped (only the local tag/attribute name is kept).
+fn node_to_value(
+    node: roxmltree::Node,
+    attribute_prefix: &str,
+    text_key: &str,
+    depth: usize,
+) -> Result<Value> {
+    if depth > MAX_NESTING_DEPTH {
+        Err(anyhow!(
+            "XML document exceeds the maximum nesting depth of {}",
+            MAX_NESTING_DEPTH
+        ))?;
+    }
+
+    let mut object = Map::new();
+
+    for attr in node.attributes() {
+        object.insert(
+            format!("{}{}", attribute_prefix, attr.name()),
+            Value::String(attr.value().to_string()),
+        );
+    }
+
+    let mut text_content = String::new();
+    let mut has_element_children = false;
+    for child in node.children() {
+        if child.is_text() {
+            text_content.push_str(child.text().unwrap_or(""));
+            continue;
+        }
+        if !child.is_element() {
+            continue;
+        }
+        has_element_children = true;
+        let child_name = child.tag_name().name().to_string();
+        let child_value = node_to_value(child, attribute_prefix, text_key, depth + 1)?;
+        match object.remove(&child_name) {
+            Some(Value::Array(mut values)) => {
+                values.push(child_value);
+                object.insert(child_name, Value::Array(values));
+            }
+            Some(existing) => {
+                object.insert(child_name, Value::Array(vec![existing, child_value]));
+            }
+            None => {
+                object.insert(child_name, child_value);
+            }
+        }
+    }
+
+    let trimmed = text_content.trim();
+
+    // An element with no attributes and no element children is a plain text leaf (or empty): it
+    // collapses to a bare string rather than an object wrapping it under `text_key`. Once there's
+    // an attribute or a child element to carry alongside the text, it has to stay an object.
+    if object.is_empty() && !has_element_children {
+        return Ok(if trimmed.is_empty() {
+            Value::Null
+        } else {
+            Value::String(trimmed.to_string())
+        });
+    }
+
+    if !trimmed.is_empty() {
+        object.insert(text_key.to_string(), Value::String(trimmed.to_string()));
+    }
+
+    Ok(if object.is_empty() {
+        Value::Null
+    } else {
+        Value::Object(object)
+    })
+}
+
+pub fn xml_to_value(xml: &str, attribute_prefix: &str, text_key: &str) -> Result<Value> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| anyhow!("Invalid XML: {}", e))?;
+    let root = doc.root_element();
+
+    let mut object = Map::new();
+    object.insert(
+        root.tag_name().name().to_string(),
+        node_to_value(root, attribute_prefix, text_key, 0)?,
+    );
+
+    Ok(Value::Object(object))
+}
+
+#[derive(Clone)]
+pub struct Xml {
+    text: String,
+    attribute_prefix: String,
+    text_key: String,
+}
+
+impl Xml {
+    pub fn parse(block_pair: Pair<Rule>) -> Result<Self> {
+        let mut text: Option<String> = None;
+        let mut attribute_prefix = String::from("@");
+        let mut text_key = String::from("$text");
+
+        for pair in block_pair.into_inner() {
+            match pair.as_rule() {
+                Rule::pair => {
+                    let (key, value) = parse_pair(pair)?;
+                    match key.as_str() {
+                        "text" => text = Some(value),
+                        "attribute_prefix" => attribute_prefix = value,
+                        "text_key" => text_key = value,
+                        _ => Err(anyhow!("Unexpected `{}` in `xml` block", key))?,
+                    }
+                }
+                Rule::expected => {
+                    // `xml` blocks do not support output expectations.
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if text.is_none() {
+            Err(anyhow!("Missing required `text` in `xml` block"))?;
+        }
+
+        Ok(Xml {
+            text: text.unwrap(),
+            attribute_prefix,
+            text_key,
+        })
+    }
+}
+
+#[async_trait]
+impl Block for Xml {
+    fn block_type(&self) -> BlockType {
+        BlockType::Xml
+    }
+
+    fn inner_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update("xml".as_bytes());
+        hasher.update(self.text.as_bytes());
+        hasher.update(self.attribute_prefix.as_bytes());
+        hasher.update(self.text_key.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    async fn execute(
+        &self,
+        _name: &str,
+        env: &Env,
+        _event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        // XML-to-JSON conversion is a pure function of `text` (itself already resolved from
+        // prior block state), so unlike side-effecting blocks it has nothing to gain from
+        // capture/replay and doesn't consult `env.execution_mode`.
+        let text = replace_variables_in_string(&self.text, "text", env)?;
+        xml_to_value(&text, &self.attribute_prefix, &self.text_key)
+    }
+
+    fn clone_box(&self) -> Box<dyn Block + Sync + Send> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_to_value_simple_test() -> Result<()> {
+        let value = xml_to_value(r#"<root><a>1</a><b>2</b></root>"#, "@", "$text")?;
+        assert_eq!(value, serde_json::json!({"root": {"a": "1", "b": "2"}}));
+        Ok(())
+    }
+
+    #[test]
+    fn xml_to_value_repeated_siblings_test() -> Result<()> {
+        let value = xml_to_value(
+            r#"<root><item>1</item><item>2</item><item>3</item></root>"#,
+            "@",
+            "$text",
+        )?;
+        assert_eq!(
+            value,
+            serde_json::json!({"root": {"item": ["1", "2", "3"]}})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn xml_to_value_attributes_test() -> Result<()> {
+        let value = xml_to_value(r#"<root id="42" kind="x"><a>1</a></root>"#, "@", "$text")?;
+        assert_eq!(
+            value,
+            serde_json::json!({"root": {"@id": "42", "@kind": "x", "a": "1"}})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn xml_to_value_mixed_content_test() -> Result<()> {
+        let value = xml_to_value(r#"<root>hello <b>world</b></root>"#, "@", "$text")?;
+        assert_eq!(
+            value,
+            serde_json::json!({"root": {"b": "world", "$text": "hello"}})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn xml_to_value_empty_element_test() -> Result<()> {
+        let value = xml_to_value(r#"<root><empty/></root>"#, "@", "$text")?;
+        assert_eq!(value, serde_json::json!({"root": {"empty": null}}));
+        Ok(())
+    }
+
+    #[test]
+    fn xml_to_value_namespace_prefixed_test() -> Result<()> {
+        let value = xml_to_value(
+            r#"<root xmlns:ns="http://example.com/ns"><ns:a>1</ns:a></root>"#,
+            "@",
+            "$text",
+        )?;
+        assert_eq!(value, serde_json::json!({"root": {"a": "1"}}));
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use super::Credentials;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+// A named OAuth2 credential: either a client-credentials grant (no `refresh_token`) or a
+// refresh-token grant. Blocks reference these by name from their auth config; the access token
+// itself is never part of the spec and is fetched (and refreshed) on demand.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OAuth2Credential {
+    pub client_id: String,
+    pub client_secret: String,
+    pub token_url: String,
+    pub scope: Option<String>,
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    access_token: String,
+    expires_at: u64,
+}
+
+// Per-run cache of access tokens fetched for OAuth2 credentials, keyed by credential name so
+// multiple blocks referencing the same credential share a single token and refresh together.
+#[derive(Clone)]
+pub struct OAuth2TokenCache {
+    tokens: Arc<Mutex<HashMap<String, CachedToken>>>,
+    // One lock per credential name, created on first use. `bearer_token` holds only the lock for
+    // its own `name` across the fetch, so credentials are only ever serialized against themselves
+    // — fetching token A never blocks a concurrent fetch of token B.
+    locks: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
+}
+
+impl OAuth2TokenCache {
+    pub fn new() -> Self {
+        OAuth2TokenCache {
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+            locks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // Returns (creating if needed) the per-credential lock for `name`.
+    async fn lock_for(&self, name: &str) -> Arc<Mutex<()>> {
+        let mut locks = self.locks.lock().await;
+        locks
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+
+    // Returns a valid bearer token for `name`, serving it from cache if it has not yet expired,
+    // and otherwise fetching (client-credentials grant) or refreshing (refresh-token grant) a new
+    // one from `credential.token_url`. Only the lock for this specific `name` is held across the
+    // fetch (not just the check and the insert), so concurrent callers sharing a credential — the
+    // common case under `Map` fan-out — serialize on a single in-flight fetch instead of each
+    // seeing "expired" and firing a duplicate request (fatal for refresh tokens that get
+    // invalidated on use), while callers for other credentials proceed concurrently.
+    pub async fn bearer_token(&self, name: &str, credential: &OAuth2Credential) -> Result<String> {
+        let lock = self.lock_for(name).await;
+        let _guard = lock.lock().await;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        if let Some(cached) = self.tokens.lock().await.get(name) {
+            if cached.expires_at > now {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let mut params = HashMap::new();
+        params.insert("client_id", credential.client_id.as_str());
+        params.insert("client_secret", credential.client_secret.as_str());
+        if let Some(scope) = &credential.scope {
+            params.insert("scope", scope.as_str());
+        }
+        match &credential.refresh_token {
+            Some(refresh_token) => {
+                params.insert("grant_type", "refresh_token");
+                params.insert("refresh_token", refresh_token.as_str());
+            }
+            None => {
+                params.insert("grant_type", "client_credentials");
+            }
+        };
+
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+            expires_in: Option<u64>,
+        }
+
+        let token: TokenResponse = reqwest::Client::new()
+            .post(&credential.token_url)
+            .form(&params)
+            .send()
+            .await?
+            .json()
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to fetch OAuth2 token for `{}` from `{}`: {}",
+                    name,
+                    credential.token_url,
+                    e
+                )
+            })?;
+
+        self.tokens.lock().await.insert(
+            name.to_string(),
+            CachedToken {
+                access_token: token.access_token.clone(),
+                expires_at: now + token.expires_in.unwrap_or(3600),
+            },
+        );
+
+        Ok(token.access_token)
+    }
+}
+
+impl Credentials {
+    // Resolves `name` as an OAuth2 credential (client-credentials or refresh-token grant, stored
+    // as the credential's JSON under that name) and returns a valid bearer token for it, fetching
+    // or refreshing via `tokens` as needed. This is the one path `Curl`/`GraphQL` blocks should
+    // use to name an OAuth2-protected auth config, rather than each block deserializing
+    // `OAuth2Credential` itself.
+    pub async fn oauth2_bearer_token(
+        &self,
+        name: &str,
+        tokens: &OAuth2TokenCache,
+    ) -> Result<String> {
+        let raw = self
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown credential `{}`", name))?;
+        let credential: OAuth2Credential = serde_json::from_str(&raw).map_err(|e| {
+            anyhow!(
+                "Credential `{}` is not a valid OAuth2 credential: {}",
+                name,
+                e
+            )
+        })?;
+        tokens.bearer_token(name, &credential).await
+    }
+}
@@ -0,0 +1,100 @@
+use crate::blocks::block::{reload_block, Block, BlockType, Env};
+use crate::Rule;
+use anyhow::{anyhow, Result};
+use pest::iterators::Pair;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::SystemTime;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
+
+// Holds the spec file backing a run along with the `Box<dyn Block>` resolved for each of its
+// block names. `resolve_and_execute` is what the run engine calls in place of a bare
+// `block.execute(...)` for each block it steps through (or before each `Map` iteration still
+// pending): it re-parses `block_pair` first and, if the re-parsed block differs from the registered
+// one (by type or `inner_hash()`), swaps in the reloaded block via `reload_block`, leaving blocks
+// whose content is unchanged (and whatever state/outputs they've already captured) untouched. It
+// does not itself look at the file's mtime; `has_changed` is there for that.
+pub struct SpecRegistry {
+    path: String,
+    modified_at: Mutex<SystemTime>,
+    blocks: Mutex<HashMap<String, Box<dyn Block + Sync + Send>>>,
+}
+
+impl SpecRegistry {
+    pub fn new(
+        path: &str,
+        modified_at: SystemTime,
+        blocks: HashMap<String, Box<dyn Block + Sync + Send>>,
+    ) -> Self {
+        SpecRegistry {
+            path: path.to_string(),
+            modified_at: Mutex::new(modified_at),
+            blocks: Mutex::new(blocks),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    // Returns `true` if the spec file's mtime has moved past what the registry last observed,
+    // updating its bookkeeping so the caller knows to re-parse and call `reload` for each block
+    // pair found in the new spec.
+    pub async fn has_changed(&self, current_modified_at: SystemTime) -> bool {
+        let mut modified_at = self.modified_at.lock().await;
+        if current_modified_at > *modified_at {
+            *modified_at = current_modified_at;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Called by the run engine for each block immediately before it executes. Re-parses
+    // `block_pair` and, if its content actually changed, swaps the registry's entry for `name` so
+    // the engine executes the reloaded definition for this call (and any later one, e.g. a
+    // subsequent `Map` iteration) instead of the stale one.
+    pub async fn reload(&self, name: &str, t: BlockType, block_pair: Pair<Rule>) -> Result<()> {
+        let mut blocks = self.blocks.lock().await;
+        let current = match blocks.get(name) {
+            Some(current) => current,
+            // Not a block this registry was built with (e.g. newly added in the edited spec);
+            // nothing to diff against, so just parse it in directly.
+            None => {
+                let parsed = crate::blocks::block::parse_block(t, block_pair)?;
+                blocks.insert(name.to_string(), parsed);
+                return Ok(());
+            }
+        };
+        if let Some(reloaded) = reload_block(t, block_pair, current.as_ref())? {
+            blocks.insert(name.to_string(), reloaded);
+        }
+        Ok(())
+    }
+
+    // Returns a clone of the block currently registered under `name`, reflecting the latest
+    // `reload`, or `None` if this registry has never seen that name.
+    pub async fn get(&self, name: &str) -> Option<Box<dyn Block + Sync + Send>> {
+        self.blocks.lock().await.get(name).cloned()
+    }
+
+    // Reloads `name` against `block_pair` (see `reload`) and then executes whichever definition
+    // is current afterwards. This is the single entry point the run engine should call per block
+    // so that a spec edit picked up mid-run is honored on the very next execution of that block.
+    pub async fn resolve_and_execute(
+        &self,
+        name: &str,
+        t: BlockType,
+        block_pair: Pair<Rule>,
+        env: &Env,
+        event_sender: Option<UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        self.reload(name, t, block_pair).await?;
+        let block = self
+            .get(name)
+            .await
+            .ok_or_else(|| anyhow!("Block `{}` not found in spec registry after reload", name))?;
+        block.execute(name, env, event_sender).await
+    }
+}
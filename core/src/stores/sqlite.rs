@@ -0,0 +1,38 @@
+use crate::stores::store::Store;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone)]
+pub struct SQLiteStore {
+    block_captures: Arc<Mutex<HashMap<String, Value>>>,
+}
+
+impl SQLiteStore {
+    pub fn new_in_memory() -> Result<Self> {
+        Ok(SQLiteStore {
+            block_captures: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+}
+
+#[async_trait]
+impl Store for SQLiteStore {
+    async fn get_block_capture(&self, key: &str) -> Result<Option<Value>> {
+        Ok(self.block_captures.lock().unwrap().get(key).cloned())
+    }
+
+    async fn store_block_capture(&self, key: &str, value: &Value) -> Result<()> {
+        self.block_captures
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.clone());
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Store + Sync + Send> {
+        Box::new(self.clone())
+    }
+}
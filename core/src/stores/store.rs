@@ -0,0 +1,20 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+
+// Backing store for run state. Block capture/replay (see `blocks::block::ExecutionMode`) persists
+// recorded `Value`s here, keyed by `Block::capture_replay_key`, so a later `Replay` run can serve
+// them back without re-invoking the side-effecting block that produced them.
+#[async_trait]
+pub trait Store {
+    async fn get_block_capture(&self, key: &str) -> Result<Option<Value>>;
+    async fn store_block_capture(&self, key: &str, value: &Value) -> Result<()>;
+
+    fn clone_box(&self) -> Box<dyn Store + Sync + Send>;
+}
+
+impl Clone for Box<dyn Store + Sync + Send> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}